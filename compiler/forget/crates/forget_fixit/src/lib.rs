@@ -0,0 +1,190 @@
+//! Machine-applicable fixes for compiler diagnostics.
+//!
+//! Diagnostics produced by `analyze`/`build` can attach one or more
+//! [`Suggestion`]s describing a textual edit that resolves (or partially
+//! resolves) the problem. [`apply_suggestions`] takes a batch of
+//! suggestions gathered from a single source file and splices them into
+//! the original text, rustfix-style: conflicting edits are rejected
+//! rather than silently corrupting the output.
+
+use std::fmt;
+use std::ops::Range;
+
+/// A half-open byte range into the original source text.
+pub type ByteRange = Range<usize>;
+
+/// How confident the compiler is that applying a [`Suggestion`] is safe.
+///
+/// Named and ordered the same way rustc's `Applicability` is, since tools
+/// consuming our JSON diagnostics (editors, codemods) already know this
+/// vocabulary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Applicability {
+    /// The suggestion is guaranteed to preserve the program's behavior and
+    /// can be applied automatically, e.g. by a `--fix` flag or IDE
+    /// "quick fix".
+    MachineApplicable,
+    /// The suggestion is syntactically valid but may change behavior, so a
+    /// human should review it before applying.
+    MaybeIncorrect,
+    /// The suggestion contains placeholder text (e.g. `/* value */`) that
+    /// must be filled in by hand before the result is valid.
+    HasPlaceholders,
+}
+
+/// A single proposed edit: replace `span` in the original source with
+/// `replacement`.
+///
+/// A zero-width `span` (`start == end`) is a pure insertion at that byte
+/// offset.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Suggestion {
+    pub span: ByteRange,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+/// Errors that prevent a batch of suggestions from being applied.
+#[derive(Debug, thiserror::Error)]
+pub enum ApplySuggestionsError {
+    #[error("suggestion span {span:?} is out of bounds for source of length {source_len}")]
+    SpanOutOfBounds { span: ByteRange, source_len: usize },
+
+    #[error("suggestion span {span:?} does not fall on a UTF-8 character boundary")]
+    InvalidCharBoundary { span: ByteRange },
+}
+
+/// A suggestion that was dropped because its span overlapped one already
+/// accepted earlier in the batch.
+#[derive(Debug, Clone)]
+pub struct RejectedSuggestion {
+    pub suggestion: Suggestion,
+    /// Span of the previously-accepted suggestion it conflicts with.
+    pub conflicts_with: ByteRange,
+}
+
+impl fmt::Display for RejectedSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "suggestion at {:?} conflicts with suggestion at {:?}; skipping",
+            self.suggestion.span, self.conflicts_with
+        )
+    }
+}
+
+/// Result of [`apply_suggestions`]: the rewritten source plus any
+/// suggestions that were skipped due to overlapping spans.
+#[derive(Debug)]
+pub struct AppliedSuggestions {
+    pub source: String,
+    pub rejected: Vec<RejectedSuggestion>,
+}
+
+/// Applies `suggestions` to `source`, returning the rewritten text.
+///
+/// Suggestions are sorted by start offset; when two suggestions' spans
+/// overlap, only the first (by start offset, ties broken by the order
+/// given) is kept and the rest are reported in
+/// [`AppliedSuggestions::rejected`]. The surviving edits are spliced in
+/// descending start-offset order so that applying one edit never shifts
+/// the byte offsets of edits still to come.
+pub fn apply_suggestions(
+    source: &str,
+    suggestions: &[Suggestion],
+) -> Result<AppliedSuggestions, ApplySuggestionsError> {
+    for suggestion in suggestions {
+        let span = &suggestion.span;
+        if span.start > span.end || span.end > source.len() {
+            return Err(ApplySuggestionsError::SpanOutOfBounds {
+                span: span.clone(),
+                source_len: source.len(),
+            });
+        }
+        if !source.is_char_boundary(span.start) || !source.is_char_boundary(span.end) {
+            return Err(ApplySuggestionsError::InvalidCharBoundary { span: span.clone() });
+        }
+    }
+
+    let mut ordered: Vec<&Suggestion> = suggestions.iter().collect();
+    ordered.sort_by_key(|suggestion| suggestion.span.start);
+
+    let mut accepted: Vec<&Suggestion> = Vec::with_capacity(ordered.len());
+    let mut rejected = Vec::new();
+    let mut last_end = 0;
+    for suggestion in ordered {
+        if suggestion.span.start < last_end {
+            rejected.push(RejectedSuggestion {
+                suggestion: suggestion.clone(),
+                conflicts_with: accepted
+                    .last()
+                    .map(|previous| previous.span.clone())
+                    .unwrap_or(0..0),
+            });
+            continue;
+        }
+        last_end = suggestion.span.end;
+        accepted.push(suggestion);
+    }
+
+    let mut fixed = source.to_string();
+    for suggestion in accepted.iter().rev() {
+        fixed.replace_range(suggestion.span.clone(), &suggestion.replacement);
+    }
+
+    Ok(AppliedSuggestions {
+        source: fixed,
+        rejected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(span: ByteRange, replacement: &str) -> Suggestion {
+        Suggestion {
+            span,
+            replacement: replacement.to_string(),
+            applicability: Applicability::MachineApplicable,
+        }
+    }
+
+    #[test]
+    fn applies_non_overlapping_edits_in_any_order() {
+        let source = "let x = 1;";
+        let suggestions = vec![suggestion(8..9, "2"), suggestion(4..5, "y")];
+        let result = apply_suggestions(source, &suggestions).unwrap();
+        assert_eq!(result.source, "let y = 2;");
+        assert!(result.rejected.is_empty());
+    }
+
+    #[test]
+    fn zero_width_span_inserts() {
+        let source = "let x = 1;";
+        let suggestions = vec![suggestion(3..3, " /* mut */")];
+        let result = apply_suggestions(source, &suggestions).unwrap();
+        assert_eq!(result.source, "let /* mut */ x = 1;");
+    }
+
+    #[test]
+    fn rejects_overlapping_suggestions_keeping_the_first() {
+        let source = "abcdef";
+        let suggestions = vec![suggestion(1..3, "XX"), suggestion(2..4, "YY")];
+        let result = apply_suggestions(source, &suggestions).unwrap();
+        assert_eq!(result.source, "aXXdef");
+        assert_eq!(result.rejected.len(), 1);
+        assert_eq!(result.rejected[0].suggestion.span, 2..4);
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_span() {
+        let source = "abc";
+        let suggestions = vec![suggestion(2..10, "x")];
+        assert!(matches!(
+            apply_suggestions(source, &suggestions),
+            Err(ApplySuggestionsError::SpanOutOfBounds { .. })
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! Parses compiletest-style `//~` annotations embedded in fixture source
+//! and matches them against the diagnostics a fixture actually produces.
+//!
+//! A fixture opts into checking by adding `//~ ERROR <substring>` on the
+//! line where a diagnostic is expected, or `//~^ ERROR <substring>` (one
+//! or more carets) to point at a preceding line, mirroring how rustc's
+//! `compiletest` annotates its UI tests. A leading `// mode: pass` or
+//! `// mode: fail` directive selects whether the fixture must compile
+//! clean or must reject the input; fixtures with no directive default
+//! to `pass`.
+
+/// Whether a fixture is expected to compile without diagnostics, or to
+/// reject the input (and therefore carry `//~` annotations).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Pass,
+    Fail,
+}
+
+/// A `//~ ERROR <substring>` annotation anchored to a 1-indexed source
+/// line.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub line: usize,
+    pub message: String,
+}
+
+/// A diagnostic actually emitted by the pipeline, reduced to what an
+/// [`Annotation`] can match against.
+#[derive(Debug, Clone)]
+pub struct EmittedDiagnostic {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Reads the `// mode: pass` / `// mode: fail` directive from the first
+/// few lines of `source`. Defaults to [`Mode::Pass`] when absent.
+pub fn parse_mode(source: &str) -> Mode {
+    for line in source.lines().take(5) {
+        if let Some(rest) = line.trim().strip_prefix("// mode:") {
+            match rest.trim() {
+                "fail" => return Mode::Fail,
+                "pass" => return Mode::Pass,
+                _ => {}
+            }
+        }
+    }
+    Mode::Pass
+}
+
+/// Extracts every `//~`/`//~^` annotation from `source`.
+///
+/// `//~ ERROR <substring>` anchors to the line it appears on; each
+/// leading `^` in e.g. `//~^ ERROR <substring>` walks one line further
+/// back, so `//~^^` anchors two lines above the comment.
+pub fn parse_annotations(source: &str) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    for (ix, line) in source.lines().enumerate() {
+        let line_number = ix + 1;
+        let Some(marker) = line.find("//~") else {
+            continue;
+        };
+        let rest = &line[marker + "//~".len()..];
+        let carets = rest.chars().take_while(|c| *c == '^').count();
+        let rest = rest[carets..].trim_start();
+        let rest = rest.strip_prefix("ERROR").unwrap_or(rest).trim_start();
+        let anchor = line_number.saturating_sub(carets);
+        annotations.push(Annotation {
+            line: anchor,
+            message: rest.to_string(),
+        });
+    }
+    annotations
+}
+
+/// The result of matching [`Annotation`]s against [`EmittedDiagnostic`]s:
+/// every annotation must be satisfied by some diagnostic on the same
+/// line whose message contains the annotation's substring, and every
+/// diagnostic must in turn be claimed by some annotation.
+#[derive(Debug, Default)]
+pub struct MatchReport {
+    pub unmatched_annotations: Vec<Annotation>,
+    pub unmatched_diagnostics: Vec<EmittedDiagnostic>,
+}
+
+impl MatchReport {
+    pub fn is_ok(&self) -> bool {
+        self.unmatched_annotations.is_empty() && self.unmatched_diagnostics.is_empty()
+    }
+}
+
+impl std::fmt::Display for MatchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for annotation in &self.unmatched_annotations {
+            writeln!(
+                f,
+                "line {}: expected a diagnostic containing {:?}, but none was emitted",
+                annotation.line, annotation.message
+            )?;
+        }
+        for diagnostic in &self.unmatched_diagnostics {
+            writeln!(
+                f,
+                "line {}: diagnostic {:?} has no matching `//~` annotation",
+                diagnostic.line, diagnostic.message
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Matches `annotations` against `diagnostics`, consuming each
+/// diagnostic at most once so two annotations can't both be satisfied
+/// by the same emitted diagnostic.
+pub fn match_diagnostics(
+    annotations: &[Annotation],
+    diagnostics: &[EmittedDiagnostic],
+) -> MatchReport {
+    let mut claimed = vec![false; diagnostics.len()];
+    let mut unmatched_annotations = Vec::new();
+
+    for annotation in annotations {
+        let found = diagnostics.iter().enumerate().position(|(ix, diagnostic)| {
+            !claimed[ix]
+                && diagnostic.line == annotation.line
+                && diagnostic.message.contains(&annotation.message)
+        });
+        match found {
+            Some(ix) => claimed[ix] = true,
+            None => unmatched_annotations.push(annotation.clone()),
+        }
+    }
+
+    let unmatched_diagnostics = diagnostics
+        .iter()
+        .zip(claimed.iter())
+        .filter(|(_, claimed)| !**claimed)
+        .map(|(diagnostic, _)| diagnostic.clone())
+        .collect();
+
+    MatchReport {
+        unmatched_annotations,
+        unmatched_diagnostics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_same_line_and_caret_annotations() {
+        let source = "let x = y;\n//~ ERROR unresolved reference `y`\nlet z = 1; //~^ ERROR also here\n";
+        let annotations = parse_annotations(source);
+        assert_eq!(annotations.len(), 2);
+        assert_eq!(annotations[0].line, 2);
+        assert_eq!(annotations[0].message, "unresolved reference `y`");
+        assert_eq!(annotations[1].line, 2);
+        assert_eq!(annotations[1].message, "also here");
+    }
+
+    #[test]
+    fn defaults_to_pass_mode() {
+        assert_eq!(parse_mode("function f() {}\n"), Mode::Pass);
+        assert_eq!(parse_mode("// mode: fail\nfunction f() {}\n"), Mode::Fail);
+    }
+
+    #[test]
+    fn reports_unmatched_annotations_and_diagnostics() {
+        let annotations = vec![Annotation {
+            line: 1,
+            message: "expected".to_string(),
+        }];
+        let diagnostics = vec![EmittedDiagnostic {
+            line: 2,
+            message: "unexpected".to_string(),
+        }];
+        let report = match_diagnostics(&annotations, &diagnostics);
+        assert!(!report.is_ok());
+        assert_eq!(report.unmatched_annotations.len(), 1);
+        assert_eq!(report.unmatched_diagnostics.len(), 1);
+    }
+}
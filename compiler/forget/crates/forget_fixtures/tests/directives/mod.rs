@@ -0,0 +1,63 @@
+//! Per-fixture directives that select which `PassManager` passes run
+//! and which of them dump the intermediate HIR, parsed from a fixture's
+//! leading comments the way rustdoc reads directives out of a code
+//! fence's info string.
+//!
+//! ```js
+//! // passes: ssa,phi,constprop
+//! // dump-after: constprop
+//! ```
+//!
+//! `passes` restricts the standard pipeline to the named subset (in
+//! pipeline order, regardless of the order listed); omitting it runs
+//! every pass. `dump-after` may be repeated or comma-separated and adds
+//! a `HIR after <name>:` section to the snapshot for each named pass.
+
+#[derive(Debug, Default)]
+pub struct Directives {
+    pub passes: Option<Vec<String>>,
+    pub dump_after: Vec<String>,
+}
+
+pub fn parse(source: &str) -> Directives {
+    let mut directives = Directives::default();
+    for line in source.lines().take(5) {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("// passes:") {
+            directives.passes = Some(split_names(rest));
+        } else if let Some(rest) = line.strip_prefix("// dump-after:") {
+            directives.dump_after.extend(split_names(rest));
+        }
+    }
+    directives
+}
+
+fn split_names(rest: &str) -> Vec<String> {
+    rest.split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_passes_and_dump_after() {
+        let source = "// passes: ssa,phi,constprop\n// dump-after: constprop\nfunction f() {}\n";
+        let directives = parse(source);
+        assert_eq!(
+            directives.passes,
+            Some(vec!["ssa".to_string(), "phi".to_string(), "constprop".to_string()])
+        );
+        assert_eq!(directives.dump_after, vec!["constprop".to_string()]);
+    }
+
+    #[test]
+    fn defaults_to_running_everything_with_no_dumps() {
+        let directives = parse("function f() {}\n");
+        assert_eq!(directives.passes, None);
+        assert!(directives.dump_after.is_empty());
+    }
+}
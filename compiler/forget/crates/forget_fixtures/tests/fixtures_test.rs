@@ -1,30 +1,90 @@
+mod annotations;
+mod directives;
+
 use std::env;
 use std::fmt::Write;
 
+use annotations::{EmittedDiagnostic, Mode};
 use forget_build_hir::build;
 use forget_estree::{ModuleItem, Statement};
+use forget_fixit::{apply_suggestions, Suggestion};
 use forget_hermes_parser::parse;
-use forget_hir::{inline_use_memo, Environment, Features, Print, Registry};
+use forget_hir::{inline_use_memo, Environment, Features, Pass, PassManager, Print, Registry};
 use forget_optimization::constant_propagation;
 use forget_semantic_analysis::analyze;
 use forget_ssa::{eliminate_redundant_phis, enter_ssa};
 use insta::{assert_snapshot, glob};
 use miette::{NamedSource, Report};
 
+/// The fixtures driver's pipeline, named so `// passes:`/`// dump-after:`
+/// directives can select and inspect individual stages. `forget_hir`
+/// can't name most of these functions itself (all but `inline_use_memo`
+/// live in the downstream `forget_ssa`/`forget_optimization` crates), so
+/// the driver wires them up the same way the hard-coded pipeline used to
+/// call them directly.
+fn standard_pass_manager() -> PassManager {
+    let mut manager = PassManager::new();
+    manager.add(Pass::new("ssa", |environment, function| {
+        enter_ssa(environment, function).map_err(|error| Box::new(error) as _)
+    }));
+    manager.add(Pass::new("phi", |environment, function| {
+        eliminate_redundant_phis(environment, function);
+        Ok(())
+    }));
+    manager.add(Pass::new("constprop", |environment, function| {
+        constant_propagation(environment, function).map_err(|error| Box::new(error) as _)
+    }));
+    manager.add(Pass::new("memo", |environment, function| {
+        inline_use_memo(environment, function).map_err(|error| Box::new(error) as _)
+    }));
+    manager
+}
+
+/// 1-indexed line number of the first labeled span of `diagnostic`,
+/// falling back to line 1 when it carries no span.
+fn diagnostic_line(source: &str, diagnostic: &impl miette::Diagnostic) -> usize {
+    let offset = diagnostic
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .map(|label| label.offset())
+        .unwrap_or(0);
+    source[..offset].matches('\n').count() + 1
+}
+
 #[test]
 fn fixtures() {
     glob!("fixtures/**.js", |path| {
         println!("fixture {}", path.to_str().unwrap());
         let input = std::fs::read_to_string(path).unwrap();
+        let mode = annotations::parse_mode(&input);
+        let expected_annotations = annotations::parse_annotations(&input);
+        let mut emitted_diagnostics: Vec<EmittedDiagnostic> = Vec::new();
+
+        let directives = directives::parse(&input);
+        let mut pass_manager = standard_pass_manager();
+        if let Some(passes) = &directives.passes {
+            pass_manager.restrict_to(passes);
+        }
+        for name in &directives.dump_after {
+            pass_manager.dump_after(name);
+        }
+
         let ast = parse(&input, path.to_str().unwrap()).unwrap();
         println!("ok parse");
 
         let mut output = String::new();
+        let mut dumps = String::new();
+        let mut suggestions: Vec<Suggestion> = Vec::new();
 
         let mut analysis = analyze(&ast, Default::default());
         let diagnostics = analysis.diagnostics();
         if !diagnostics.is_empty() {
             for diagnostic in diagnostics {
+                suggestions.extend(diagnostic.suggestions().iter().cloned());
+                emitted_diagnostics.push(EmittedDiagnostic {
+                    line: diagnostic_line(&input, &diagnostic),
+                    message: diagnostic.to_string(),
+                });
                 eprintln!(
                     "{:?}",
                     Report::new(diagnostic)
@@ -48,19 +108,22 @@ fn fixtures() {
                     match build(&environment, &fun.function) {
                         Ok(mut fun) => {
                             println!("ok build");
-                            enter_ssa(&environment, &mut fun).unwrap();
-                            println!("ok enter_ssa");
-                            eliminate_redundant_phis(&environment, &mut fun);
-                            println!("ok eliminate_redundant_phis");
-                            constant_propagation(&environment, &mut fun).unwrap();
-                            println!("ok constant_propagation");
-                            inline_use_memo(&environment, &mut fun).unwrap();
-                            println!("ok inline_use_memo");
+                            pass_manager
+                                .run(&environment, &mut fun, |name, hir| {
+                                    write!(&mut dumps, "\n\nHIR after {name}:\n{}", hir.trim())
+                                        .unwrap();
+                                })
+                                .unwrap();
+                            println!("ok passes");
                             fun.print(&fun.body, &mut output).unwrap();
                             println!("ok print");
                         }
                         Err(error) => {
                             write!(&mut output, "{}", error,).unwrap();
+                            emitted_diagnostics.push(EmittedDiagnostic {
+                                line: diagnostic_line(&input, &error),
+                                message: error.to_string(),
+                            });
                             eprintln!(
                                 "{:?}",
                                 Report::new(error).with_source_code(NamedSource::new(
@@ -76,6 +139,46 @@ fn fixtures() {
         }
 
         let output = output.trim();
-        assert_snapshot!(format!("Input:\n{input}\n\nOutput:\n{output}"));
+        let mut snapshot = format!("Input:\n{input}\n\nOutput:\n{output}");
+        snapshot.push_str(&dumps);
+
+        if !suggestions.is_empty() {
+            let suggestions_json = serde_json::to_string_pretty(&suggestions).unwrap();
+            write!(&mut snapshot, "\n\nSuggestions:\n{suggestions_json}").unwrap();
+            match apply_suggestions(&input, &suggestions) {
+                Ok(applied) => {
+                    write!(&mut snapshot, "\n\nFixed:\n{}", applied.source.trim()).unwrap();
+                    for rejected in &applied.rejected {
+                        eprintln!("{rejected}");
+                    }
+                }
+                Err(error) => {
+                    write!(&mut snapshot, "\n\nFixed: <error applying suggestions: {error}>")
+                        .unwrap();
+                }
+            }
+        }
+
+        match mode {
+            Mode::Fail => assert!(
+                !expected_annotations.is_empty(),
+                "fixture {:?} has `// mode: fail` but no `//~` annotations",
+                path
+            ),
+            Mode::Pass => assert!(
+                expected_annotations.is_empty(),
+                "fixture {:?} has `// mode: pass` but contains `//~` annotations",
+                path
+            ),
+        }
+        let report = annotations::match_diagnostics(&expected_annotations, &emitted_diagnostics);
+        assert!(
+            report.is_ok(),
+            "fixture {:?} annotation mismatch:\n{}",
+            path,
+            report
+        );
+
+        assert_snapshot!(snapshot);
     });
 }
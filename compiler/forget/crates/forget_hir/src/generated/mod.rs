@@ -0,0 +1,8 @@
+//! Node enums, visitors, and `Print` scaffolding, generated from
+//! `xtask/schema/hir_nodes.ron` by `cargo xtask codegen`. See
+//! `forget/xtask` for the generator; do not hand-edit `nodes.rs`,
+//! `visitor.rs`, or `print.rs`.
+
+pub mod nodes;
+pub mod print;
+pub mod visitor;
@@ -0,0 +1,37 @@
+// @generated by `cargo xtask codegen` from xtask/schema/hir_nodes.ron.
+// Do not edit by hand; edit the schema and regenerate instead.
+
+use crate::{BinaryOperator, Place};
+
+/// One instruction in a basic block's HIR, in SSA form once `enter_ssa` has run.
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    /// Reads the current value of `place`.
+    Load {
+        place: Place,
+    },
+    /// Writes `value` into `place`.
+    Store {
+        place: Place,
+        value: Place,
+    },
+    /// Applies `operator` to `left` and `right`.
+    BinaryOp {
+        operator: BinaryOperator,
+        left: Place,
+        right: Place,
+    },
+    /// Calls `callee` with `arguments`.
+    Call {
+        callee: Place,
+        arguments: Vec<Place>,
+    },
+    /// Merges `operands` from each predecessor block, introduced by `enter_ssa`.
+    Phi {
+        operands: Vec<Place>,
+    },
+    /// Returns `value` (or nothing) from the enclosing function.
+    Return {
+        value: Option<Place>,
+    },
+}
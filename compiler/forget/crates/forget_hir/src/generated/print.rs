@@ -0,0 +1,18 @@
+// @generated by `cargo xtask codegen` from xtask/schema/hir_nodes.ron.
+// Do not edit by hand; edit the schema and regenerate instead.
+
+use std::fmt::{self, Write};
+
+use super::nodes::*;
+
+/// Writes a debug-ish textual form of `Instruction` to `out`.
+pub fn print_instruction(node: &Instruction, out: &mut impl Write) -> fmt::Result {
+    match node {
+        Instruction::Load { place } => write!(out, "Load(place: {place:?})"),
+        Instruction::Store { place, value } => write!(out, "Store(place: {place:?}, value: {value:?})"),
+        Instruction::BinaryOp { operator, left, right } => write!(out, "BinaryOp(operator: {operator:?}, left: {left:?}, right: {right:?})"),
+        Instruction::Call { callee, arguments } => write!(out, "Call(callee: {callee:?}, arguments: {arguments:?})"),
+        Instruction::Phi { operands } => write!(out, "Phi(operands: {operands:?})"),
+        Instruction::Return { value } => write!(out, "Return(value: {value:?})"),
+    }
+}
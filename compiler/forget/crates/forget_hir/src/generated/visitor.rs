@@ -0,0 +1,46 @@
+// @generated by `cargo xtask codegen` from xtask/schema/hir_nodes.ron.
+// Do not edit by hand; edit the schema and regenerate instead.
+
+use super::nodes::*;
+
+pub trait Visitor {
+    fn visit_load(&mut self, _node: &Instruction) {}
+    fn visit_store(&mut self, _node: &Instruction) {}
+    fn visit_binary_op(&mut self, _node: &Instruction) {}
+    fn visit_call(&mut self, _node: &Instruction) {}
+    fn visit_phi(&mut self, _node: &Instruction) {}
+    fn visit_return(&mut self, _node: &Instruction) {}
+}
+
+/// Dispatches `Instruction` to the matching `Visitor::visit_*` method.
+pub fn walk_instruction(visitor: &mut impl Visitor, node: &Instruction) {
+    match node {
+        Instruction::Load { .. } => visitor.visit_load(node),
+        Instruction::Store { .. } => visitor.visit_store(node),
+        Instruction::BinaryOp { .. } => visitor.visit_binary_op(node),
+        Instruction::Call { .. } => visitor.visit_call(node),
+        Instruction::Phi { .. } => visitor.visit_phi(node),
+        Instruction::Return { .. } => visitor.visit_return(node),
+    }
+}
+
+pub trait VisitorMut {
+    fn visit_load_mut(&mut self, _node: &mut Instruction) {}
+    fn visit_store_mut(&mut self, _node: &mut Instruction) {}
+    fn visit_binary_op_mut(&mut self, _node: &mut Instruction) {}
+    fn visit_call_mut(&mut self, _node: &mut Instruction) {}
+    fn visit_phi_mut(&mut self, _node: &mut Instruction) {}
+    fn visit_return_mut(&mut self, _node: &mut Instruction) {}
+}
+
+/// Dispatches `Instruction` to the matching `VisitorMut::visit_*` method.
+pub fn walk_instruction_mut(visitor: &mut impl VisitorMut, node: &mut Instruction) {
+    match node {
+        Instruction::Load { .. } => visitor.visit_load_mut(node),
+        Instruction::Store { .. } => visitor.visit_store_mut(node),
+        Instruction::BinaryOp { .. } => visitor.visit_binary_op_mut(node),
+        Instruction::Call { .. } => visitor.visit_call_mut(node),
+        Instruction::Phi { .. } => visitor.visit_phi_mut(node),
+        Instruction::Return { .. } => visitor.visit_return_mut(node),
+    }
+}
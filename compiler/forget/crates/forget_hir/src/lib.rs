@@ -0,0 +1,4 @@
+pub mod generated;
+mod pass_manager;
+
+pub use pass_manager::{Pass, PassError, PassManager};
@@ -0,0 +1,128 @@
+//! A small, ordered pipeline of named HIR passes.
+//!
+//! The fixtures test driver used to hard-code the sequence `enter_ssa`,
+//! `eliminate_redundant_phis`, `constant_propagation`, `inline_use_memo`
+//! with no way to run a subset, reorder them, or inspect intermediate
+//! state. [`PassManager`] names each step so callers (in practice, the
+//! fixtures driver reading `// passes: ssa,phi,constprop` and
+//! `// dump-after: constprop` directives) can enable/disable individual
+//! passes and capture the HIR immediately after any one of them runs.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::{Environment, Function, Print};
+
+/// Error returned by an individual pass.
+pub type PassError = Box<dyn std::error::Error>;
+
+/// A single named transformation over a [`Function`]'s HIR.
+pub struct Pass {
+    name: &'static str,
+    run: Box<dyn Fn(&Environment, &mut Function) -> Result<(), PassError>>,
+}
+
+impl Pass {
+    /// Wraps `run` as a pass named `name`. Passes that cannot fail
+    /// (like `eliminate_redundant_phis`) should return `Ok(())`.
+    pub fn new(
+        name: &'static str,
+        run: impl Fn(&Environment, &mut Function) -> Result<(), PassError> + 'static,
+    ) -> Self {
+        Self {
+            name,
+            run: Box::new(run),
+        }
+    }
+}
+
+impl fmt::Debug for Pass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pass").field("name", &self.name).finish()
+    }
+}
+
+/// An ordered list of [`Pass`]es, each independently enabled/disabled,
+/// run in sequence over one [`Function`].
+#[derive(Default)]
+pub struct PassManager {
+    passes: Vec<Pass>,
+    enabled: HashSet<&'static str>,
+    dump_after: HashSet<&'static str>,
+}
+
+impl PassManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `pass` to the pipeline, enabled by default.
+    ///
+    /// `forget_hir` has no business depending on the passes downstream
+    /// crates implement (`forget_ssa::enter_ssa`,
+    /// `forget_optimization::constant_propagation`, ...) — that would
+    /// be a circular crate dependency. Callers that want the fixtures
+    /// driver's usual SSA/phi/constprop/memo pipeline build it by
+    /// `add`-ing those crates' functions themselves, in order.
+    pub fn add(&mut self, pass: Pass) -> &mut Self {
+        self.enabled.insert(pass.name);
+        self.passes.push(pass);
+        self
+    }
+
+    /// Enables or disables the pass named `name`; unknown names are
+    /// ignored so a directive's pass list doesn't have to be validated
+    /// against exactly which passes a particular manager registered.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(pass) = self.passes.iter().find(|pass| pass.name == name) {
+            if enabled {
+                self.enabled.insert(pass.name);
+            } else {
+                self.enabled.remove(pass.name);
+            }
+        }
+    }
+
+    /// Disables every pass except those named in `names`, preserving
+    /// pipeline order. Used for a `// passes: ssa,phi` directive that
+    /// selects a subset to run in isolation.
+    pub fn restrict_to(&mut self, names: &[String]) {
+        let keep: HashSet<&str> = names.iter().map(String::as_str).collect();
+        let enabled: Vec<&'static str> = self
+            .passes
+            .iter()
+            .map(|pass| pass.name)
+            .filter(|name| keep.contains(name))
+            .collect();
+        self.enabled = enabled.into_iter().collect();
+    }
+
+    /// Marks `name` so the HIR is captured immediately after it runs.
+    pub fn dump_after(&mut self, name: &str) {
+        if let Some(pass) = self.passes.iter().find(|pass| pass.name == name) {
+            self.dump_after.insert(pass.name);
+        }
+    }
+
+    /// Runs every enabled pass in order, calling `on_dump(name, hir)`
+    /// after any pass marked via [`PassManager::dump_after`].
+    pub fn run(
+        &self,
+        environment: &Environment,
+        function: &mut Function,
+        mut on_dump: impl FnMut(&str, &str),
+    ) -> Result<(), PassError> {
+        for pass in &self.passes {
+            if !self.enabled.contains(pass.name) {
+                continue;
+            }
+            (pass.run)(environment, function)?;
+            if self.dump_after.contains(pass.name) {
+                let mut hir = String::new();
+                let _ = function.print(&function.body, &mut hir);
+                on_dump(pass.name, &hir);
+            }
+        }
+        Ok(())
+    }
+}
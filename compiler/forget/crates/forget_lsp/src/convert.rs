@@ -0,0 +1,75 @@
+//! Conversion between byte offsets (what miette spans and our
+//! [`forget_fixit::Suggestion`]s use) and LSP's UTF-16 line/column
+//! `Position`s.
+
+use lsp_types::{Position, Range};
+
+/// Maps byte offsets in a source string to LSP positions.
+///
+/// Built once per document version and reused for every diagnostic/code
+/// action emitted for that version, since computing it requires a linear
+/// scan of the text.
+pub struct LineIndex {
+    /// Byte offset of the start of each line.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        for (offset, ch) in text.char_indices() {
+            if ch == '\n' {
+                line_starts.push(offset + 1);
+            }
+        }
+        Self { line_starts }
+    }
+
+    /// Converts a byte offset into a `(line, utf16_column)` LSP position.
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let column_utf16 = text[line_start..offset].encode_utf16().count();
+        Position {
+            line: line as u32,
+            character: column_utf16 as u32,
+        }
+    }
+
+    /// Converts a byte range into an LSP `Range`.
+    pub fn range(&self, text: &str, span: std::ops::Range<usize>) -> Range {
+        Range {
+            start: self.position(text, span.start),
+            end: self.position(text, span.end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_positions_across_lines() {
+        let text = "let x = 1;\nlet y = 2;\n";
+        let index = LineIndex::new(text);
+        assert_eq!(
+            index.position(text, 0),
+            Position {
+                line: 0,
+                character: 0
+            }
+        );
+        let y_offset = text.find('y').unwrap();
+        assert_eq!(
+            index.position(text, y_offset),
+            Position {
+                line: 1,
+                character: 4
+            }
+        );
+    }
+}
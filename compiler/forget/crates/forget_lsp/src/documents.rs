@@ -0,0 +1,86 @@
+//! Tracks the latest pipeline result for every open document so that
+//! `textDocument/codeAction` can turn suggestions gathered during the
+//! most recent `didOpen`/`didChange` into a `WorkspaceEdit` without
+//! re-running the pipeline.
+
+use std::collections::HashMap;
+
+use forget_fixit::Applicability;
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, TextEdit, Url,
+    WorkspaceEdit,
+};
+
+use crate::convert::LineIndex;
+use crate::pipeline::PipelineResult;
+
+/// The text and most recent pipeline result for one open document.
+struct Document {
+    text: String,
+    index: LineIndex,
+    result: PipelineResult,
+}
+
+/// All documents currently open in the client, keyed by URI.
+#[derive(Default)]
+pub struct Documents {
+    documents: HashMap<Url, Document>,
+}
+
+impl Documents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the result of re-running the pipeline over `uri`'s new
+    /// `text`, replacing whatever was stored for the previous version.
+    pub fn update(&mut self, uri: Url, text: String, result: PipelineResult) {
+        let index = LineIndex::new(&text);
+        self.documents.insert(uri, Document { text, index, result });
+    }
+
+    /// Builds one code action per suggestion whose span overlaps the
+    /// requested range, offering it as a quick fix.
+    ///
+    /// Suggestions that are only `MaybeIncorrect` or `HasPlaceholders`
+    /// are surfaced too, but editors use `CodeAction::is_preferred` to
+    /// decide which one to apply automatically, so only
+    /// `MachineApplicable` ones are marked preferred.
+    pub fn code_actions(&self, params: &CodeActionParams) -> Option<Vec<CodeActionOrCommand>> {
+        let uri = &params.text_document.uri;
+        let document = self.documents.get(uri)?;
+
+        let actions = document
+            .result
+            .suggestions
+            .iter()
+            .filter(|suggestion| {
+                let range = document.index.range(&document.text, suggestion.span.clone());
+                ranges_overlap(&range, &params.range)
+            })
+            .map(|suggestion| {
+                let edit = TextEdit {
+                    range: document.index.range(&document.text, suggestion.span.clone()),
+                    new_text: suggestion.replacement.clone(),
+                };
+                let mut changes = HashMap::new();
+                changes.insert(uri.clone(), vec![edit]);
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Apply suggestion: {}", suggestion.replacement),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    is_preferred: Some(suggestion.applicability == Applicability::MachineApplicable),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(changes),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Some(actions)
+    }
+}
+
+fn ranges_overlap(a: &lsp_types::Range, b: &lsp_types::Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
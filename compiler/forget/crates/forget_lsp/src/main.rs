@@ -0,0 +1,155 @@
+//! `forget-lsp`: a language server exposing the compiler pipeline to
+//! editors.
+//!
+//! Runs the same parse -> `analyze` -> `build` -> `enter_ssa` ->
+//! optimization pipeline as the `forget_fixtures` snapshot test on every
+//! document change and publishes the resulting diagnostics, instead of
+//! only being reachable through the batch `glob!` test driver. Code
+//! actions surface the diagnostics' suggestions as `WorkspaceEdit`s,
+//! marking machine-applicable ones as preferred.
+
+mod convert;
+mod documents;
+mod pipeline;
+
+use std::error::Error;
+
+use forget_hir::Features;
+use lsp_server::{Connection, ExtractError, Message, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, Request as _};
+use lsp_types::{
+    CodeActionProviderCapability, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
+    InitializeParams, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind,
+};
+use serde::Deserialize;
+
+use documents::Documents;
+
+/// Feature flags configurable through the client's `initializationOptions`,
+/// mirroring the `Features` struct consumed by `Environment::new` in the
+/// batch pipeline.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct InitializationOptions {
+    validate_frozen_lambdas: bool,
+}
+
+impl From<InitializationOptions> for Features {
+    fn from(options: InitializationOptions) -> Self {
+        Features {
+            validate_frozen_lambdas: options.validate_frozen_lambdas,
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let server_capabilities = serde_json::to_value(ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::FULL,
+        )),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..Default::default()
+    })?;
+    let initialize_params = connection.initialize(server_capabilities)?;
+    let initialize_params: InitializeParams = serde_json::from_value(initialize_params)?;
+    let features: Features = initialize_params
+        .initialization_options
+        .map(serde_json::from_value::<InitializationOptions>)
+        .transpose()?
+        .unwrap_or_default()
+        .into();
+
+    main_loop(connection, features)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+fn main_loop(
+    connection: Connection,
+    features: Features,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    let mut documents = Documents::new();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                if let Ok((id, params)) = cast_request::<CodeActionRequest>(request) {
+                    let actions = documents.code_actions(&params);
+                    let response = Response::new_ok(id, actions.unwrap_or_default());
+                    connection.sender.send(Message::Response(response))?;
+                }
+            }
+            Message::Notification(notification) => {
+                match notification.method.as_str() {
+                    DidOpenTextDocument::METHOD => {
+                        let params: DidOpenTextDocumentParams =
+                            serde_json::from_value(notification.params)?;
+                        let uri = params.text_document.uri;
+                        let text = params.text_document.text;
+                        publish(&connection, &mut documents, uri, text, &features)?;
+                    }
+                    DidChangeTextDocument::METHOD => {
+                        let params: DidChangeTextDocumentParams =
+                            serde_json::from_value(notification.params)?;
+                        let uri = params.text_document.uri;
+                        // We advertise full-document sync, so the last change
+                        // event carries the complete text.
+                        let text = params
+                            .content_changes
+                            .into_iter()
+                            .last()
+                            .map(|change| change.text)
+                            .unwrap_or_default();
+                        publish(&connection, &mut documents, uri, text, &features)?;
+                    }
+                    _ => {}
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn publish(
+    connection: &Connection,
+    documents: &mut Documents,
+    uri: lsp_types::Url,
+    text: String,
+    features: &Features,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    // `main_loop` owns `features` for the life of the connection and
+    // borrows it out to each notification; clone the (small,
+    // config-only) value in so `pipeline::run` can build its owned
+    // `Environment` without us depending on `Features` being `Copy`.
+    let result = pipeline::run(&text, features.clone());
+    let diagnostics = result.diagnostics.clone();
+    documents.update(uri.clone(), text, result);
+
+    let params = PublishDiagnosticsParams {
+        uri,
+        diagnostics,
+        version: None,
+    };
+    connection.sender.send(Message::Notification(
+        lsp_server::Notification::new(PublishDiagnostics::METHOD.to_string(), params),
+    ))?;
+    Ok(())
+}
+
+fn cast_request<R>(request: Request) -> Result<(RequestId, R::Params), ExtractError<Request>>
+where
+    R: lsp_types::request::Request,
+    R::Params: serde::de::DeserializeOwned,
+{
+    request.extract(R::METHOD)
+}
@@ -0,0 +1,121 @@
+//! Runs the same parse -> `analyze` -> `build` -> `enter_ssa` ->
+//! optimization pipeline as the `forget_fixtures` snapshot test, but
+//! collects LSP-shaped diagnostics and suggestions instead of printing a
+//! snapshot.
+
+use forget_build_hir::build;
+use forget_estree::{ModuleItem, Statement};
+use forget_fixit::Suggestion;
+use forget_hermes_parser::parse;
+use forget_hir::{inline_use_memo, Environment, Features, Registry};
+use forget_optimization::constant_propagation;
+use forget_semantic_analysis::analyze;
+use forget_ssa::{eliminate_redundant_phis, enter_ssa};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+use miette::Severity;
+
+use crate::convert::LineIndex;
+
+/// Result of running the pipeline once over a document's current text.
+pub struct PipelineResult {
+    pub diagnostics: Vec<Diagnostic>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Runs the full pipeline over `source`, honoring `features` (populated
+/// from the client's `initializationOptions`).
+///
+/// A parse error is reported as a single diagnostic at the start of the
+/// file, and no later stage runs on that text.
+pub fn run(source: &str, features: Features) -> PipelineResult {
+    let index = LineIndex::new(source);
+    let mut diagnostics = Vec::new();
+    let mut suggestions = Vec::new();
+
+    let ast = match parse(source, "<lsp>") {
+        Ok(ast) => ast,
+        Err(error) => {
+            diagnostics.push(Diagnostic {
+                range: index.range(source, 0..0),
+                severity: Some(DiagnosticSeverity::ERROR),
+                message: error.to_string(),
+                ..Default::default()
+            });
+            return PipelineResult {
+                diagnostics,
+                suggestions,
+            };
+        }
+    };
+
+    let mut analysis = analyze(&ast, Default::default());
+    for diagnostic in analysis.diagnostics() {
+        suggestions.extend(diagnostic.suggestions().iter().cloned());
+        diagnostics.push(to_lsp_diagnostic(&index, source, &diagnostic));
+    }
+
+    let environment = Environment::new(features, Registry, analysis);
+    for item in &ast.body {
+        let ModuleItem::Statement(Statement::FunctionDeclaration(fun)) = item else {
+            continue;
+        };
+        let mut fun = match build(&environment, &fun.function) {
+            Ok(fun) => fun,
+            Err(error) => {
+                diagnostics.push(to_lsp_diagnostic(&index, source, &error));
+                continue;
+            }
+        };
+        if let Err(error) = enter_ssa(&environment, &mut fun) {
+            diagnostics.push(to_lsp_diagnostic(&index, source, &error));
+            continue;
+        }
+        eliminate_redundant_phis(&environment, &mut fun);
+        if let Err(error) = constant_propagation(&environment, &mut fun) {
+            diagnostics.push(to_lsp_diagnostic(&index, source, &error));
+            continue;
+        }
+        if let Err(error) = inline_use_memo(&environment, &mut fun) {
+            diagnostics.push(to_lsp_diagnostic(&index, source, &error));
+            continue;
+        }
+    }
+
+    PipelineResult {
+        diagnostics,
+        suggestions,
+    }
+}
+
+fn to_lsp_diagnostic(
+    index: &LineIndex,
+    source: &str,
+    diagnostic: &impl miette::Diagnostic,
+) -> Diagnostic {
+    let range = diagnostic
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .map(|label| {
+            index.range(source, label.offset()..label.offset() + label.len().max(1))
+        })
+        .unwrap_or_else(|| index.range(source, 0..0));
+
+    Diagnostic {
+        range,
+        severity: Some(to_lsp_severity(diagnostic.severity().unwrap_or(Severity::Error))),
+        code: diagnostic
+            .code()
+            .map(|code| lsp_types::NumberOrString::String(code.to_string())),
+        source: Some("forget".to_string()),
+        message: diagnostic.to_string(),
+        ..Default::default()
+    }
+}
+
+fn to_lsp_severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Advice => DiagnosticSeverity::HINT,
+    }
+}
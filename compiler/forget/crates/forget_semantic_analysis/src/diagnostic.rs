@@ -0,0 +1,96 @@
+//! The diagnostic type returned by `analyze`.
+//!
+//! Renders through `miette` for human-readable output, and may
+//! additionally carry one or more [`Suggestion`]s describing a fix —
+//! consumed by `forget_fixit::apply_suggestions` for the `Fixed:`
+//! fixture snapshot and by `forget_lsp`'s `textDocument/codeAction`.
+
+use forget_fixit::Suggestion;
+use miette::{LabeledSpan, SourceSpan};
+use thiserror::Error;
+
+/// A single diagnostic emitted by semantic analysis.
+#[derive(Debug, Clone, Error)]
+#[error("{message}")]
+pub struct Diagnostic {
+    message: String,
+    span: SourceSpan,
+    severity: miette::Severity,
+    code: Option<String>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>, span: impl Into<SourceSpan>) -> Self {
+        Self {
+            message: message.into(),
+            span: span.into(),
+            severity: miette::Severity::Error,
+            code: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    pub fn with_severity(mut self, severity: miette::Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Attaches `suggestion` as a fix for this diagnostic. Diagnostics
+    /// with more than one independent fix (e.g. "remove" vs. "rename")
+    /// can call this more than once.
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// The fixes attached to this diagnostic, if any. Empty for
+    /// diagnostics that only describe a problem without a known fix.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+}
+
+impl miette::Diagnostic for Diagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        self.code
+            .as_ref()
+            .map(|code| Box::new(code) as Box<dyn std::fmt::Display + 'a>)
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(self.severity)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(std::iter::once(LabeledSpan::underline(self.span))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forget_fixit::Applicability;
+
+    #[test]
+    fn carries_attached_suggestions() {
+        let diagnostic = Diagnostic::new("unused variable `x`", (0, 1))
+            .with_suggestion(Suggestion {
+                span: 0..1,
+                replacement: String::new(),
+                applicability: Applicability::MachineApplicable,
+            });
+        assert_eq!(diagnostic.suggestions().len(), 1);
+    }
+
+    #[test]
+    fn defaults_to_no_suggestions() {
+        let diagnostic = Diagnostic::new("parse error", (0, 0));
+        assert!(diagnostic.suggestions().is_empty());
+    }
+}
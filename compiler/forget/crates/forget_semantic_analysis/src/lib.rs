@@ -0,0 +1,79 @@
+//! Semantic analysis: walks a parsed `Program` and reports diagnostics
+//! before the pipeline moves on to `forget_build_hir::build`.
+
+mod diagnostic;
+
+use std::collections::HashMap;
+
+use forget_estree::{ModuleItem, Program, Statement};
+use forget_fixit::{Applicability, Suggestion};
+
+pub use diagnostic::Diagnostic;
+
+/// Configuration for a single [`analyze`] call. Empty today; lint-style
+/// toggles land here as rules are added, the same way `forget_hir`'s
+/// `Features` grew to gate later pipeline stages.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AnalysisOptions {}
+
+/// The outcome of running [`analyze`] once over a `Program`.
+#[derive(Debug, Default)]
+pub struct AnalysisResult {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl AnalysisResult {
+    /// The diagnostics collected while analyzing, cloned out so callers
+    /// can keep iterating them after moving `self` into
+    /// `Environment::new` (which consumes the result to seed later
+    /// pipeline stages).
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.clone()
+    }
+
+    /// Debug view of the full result, for snapshotting the whole shape
+    /// of an `analyze` call rather than just its rendered diagnostics.
+    pub fn debug(&self) -> &Vec<Diagnostic> {
+        &self.diagnostics
+    }
+}
+
+/// Walks `ast`'s top-level function declarations and reports duplicate
+/// names, since `forget_build_hir::build` keys one HIR function per
+/// name and a duplicate would silently shadow the first.
+///
+/// This is the one rule implemented so far. It attaches a
+/// `MaybeIncorrect` [`Suggestion`] removing the duplicate declaration,
+/// so `Diagnostic::suggestions()` has a real producer in the pipeline
+/// rather than only being exercised by its own unit tests.
+pub fn analyze(ast: &Program, _options: AnalysisOptions) -> AnalysisResult {
+    let mut first_declared: HashMap<String, std::ops::Range<u32>> = HashMap::new();
+    let mut diagnostics = Vec::new();
+
+    for item in &ast.body {
+        let ModuleItem::Statement(Statement::FunctionDeclaration(fun)) = item else {
+            continue;
+        };
+        let Some(name) = fun.function.id.as_ref().map(|id| id.name.clone()) else {
+            continue;
+        };
+        let range = fun.function.range.clone();
+        if first_declared.contains_key(&name) {
+            diagnostics.push(
+                Diagnostic::new(
+                    format!("function `{name}` is declared more than once"),
+                    (range.start as usize, (range.end - range.start) as usize),
+                )
+                .with_suggestion(Suggestion {
+                    span: range.start as usize..range.end as usize,
+                    replacement: String::new(),
+                    applicability: Applicability::MaybeIncorrect,
+                }),
+            );
+        } else {
+            first_declared.insert(name, range);
+        }
+    }
+
+    AnalysisResult { diagnostics }
+}
@@ -8,7 +8,7 @@ fn fixtures() {
         println!("fixture {}", path.to_str().unwrap());
         let input = std::fs::read_to_string(path).unwrap();
         let ast = parse(&input, path.to_str().unwrap()).unwrap();
-        let analysis = analyze(&ast);
+        let analysis = analyze(&ast, Default::default());
 
         let ast_output = serde_json::to_string_pretty(&ast).unwrap();
         let analysis_output = format!("{:#?}", analysis.debug());
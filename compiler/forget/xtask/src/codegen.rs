@@ -0,0 +1,194 @@
+//! Emits `forget_hir::generated::nodes` (the node enums themselves) and
+//! `forget_hir::generated::visitor` (a `Visitor`/`VisitorMut` pair with
+//! default walk implementations) from a [`Schema`].
+//!
+//! This removes the hand-written match arm per variant that every HIR
+//! pass (`constant_propagation`, `eliminate_redundant_phis`, ...) used
+//! to need and keeps them all in sync as node kinds are added: add a
+//! variant to the schema, regenerate, and every visitor gets a default
+//! (no-op) arm for it for free.
+
+use crate::schema::Schema;
+
+const HEADER: &str = "// @generated by `cargo xtask codegen` from xtask/schema/hir_nodes.ron.\n// Do not edit by hand; edit the schema and regenerate instead.\n";
+
+/// Renders the node enum/struct definitions.
+pub fn generate_nodes(schema: &Schema) -> String {
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push_str("\nuse crate::{BinaryOperator, Place};\n");
+
+    for node in &schema.nodes {
+        out.push('\n');
+        out.push_str(&format!("/// {}\n", node.doc));
+        out.push_str("#[derive(Debug, Clone)]\n");
+        out.push_str(&format!("pub enum {} {{\n", node.name));
+        for variant in &node.variants {
+            out.push_str(&format!("    /// {}\n", variant.doc));
+            if variant.fields.is_empty() {
+                out.push_str(&format!("    {},\n", variant.name));
+            } else {
+                out.push_str(&format!("    {} {{\n", variant.name));
+                for (field_name, field_type) in &variant.fields {
+                    out.push_str(&format!("        {field_name}: {field_type},\n"));
+                }
+                out.push_str("    },\n");
+            }
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// Renders the `Visitor`/`VisitorMut` traits: one `visit_<variant>`
+/// method per variant (default: no-op) plus a `walk_<node>` free
+/// function that dispatches to them, so a visitor overriding a single
+/// variant doesn't have to handle the rest.
+///
+/// `VisitorMut` is the one read-write passes like `constant_propagation`
+/// and `eliminate_redundant_phis` actually need — `Visitor` alone would
+/// only help read-only consumers like `Print`.
+pub fn generate_visitor(schema: &Schema) -> String {
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push_str("\nuse super::nodes::*;\n");
+
+    for node in &schema.nodes {
+        emit_visitor_trait(&mut out, node, VisitorMode::Shared);
+        emit_visitor_trait(&mut out, node, VisitorMode::Mut);
+    }
+    out
+}
+
+#[derive(Clone, Copy)]
+enum VisitorMode {
+    Shared,
+    Mut,
+}
+
+impl VisitorMode {
+    fn trait_name(self) -> &'static str {
+        match self {
+            VisitorMode::Shared => "Visitor",
+            VisitorMode::Mut => "VisitorMut",
+        }
+    }
+
+    fn reference(self) -> &'static str {
+        match self {
+            VisitorMode::Shared => "&",
+            VisitorMode::Mut => "&mut ",
+        }
+    }
+
+    fn method_suffix(self) -> &'static str {
+        match self {
+            VisitorMode::Shared => "",
+            VisitorMode::Mut => "_mut",
+        }
+    }
+}
+
+fn emit_visitor_trait(out: &mut String, node: &crate::schema::NodeDef, mode: VisitorMode) {
+    let node_name = &node.name;
+    let snake = to_snake_case(node_name);
+    let trait_name = mode.trait_name();
+    let reference = mode.reference();
+    let suffix = mode.method_suffix();
+
+    out.push_str(&format!("\npub trait {trait_name} {{\n"));
+    for variant in &node.variants {
+        out.push_str(&format!(
+            "    fn visit_{}{suffix}(&mut self, _node: {reference}{node_name}) {{}}\n",
+            to_snake_case(&variant.name)
+        ));
+    }
+    out.push_str("}\n");
+
+    out.push_str(&format!(
+        "\n/// Dispatches `{node_name}` to the matching `{trait_name}::visit_*` method.\n"
+    ));
+    out.push_str(&format!(
+        "pub fn walk_{snake}{suffix}(visitor: &mut impl {trait_name}, node: {reference}{node_name}) {{\n"
+    ));
+    out.push_str("    match node {\n");
+    for variant in &node.variants {
+        let pattern = if variant.fields.is_empty() {
+            format!("{}::{}", node_name, variant.name)
+        } else {
+            format!("{}::{} {{ .. }}", node_name, variant.name)
+        };
+        out.push_str(&format!(
+            "        {pattern} => visitor.visit_{}{suffix}(node),\n",
+            to_snake_case(&variant.name)
+        ));
+    }
+    out.push_str("    }\n");
+    out.push_str("}\n");
+}
+
+/// Renders one `print_<node>` function per node: a `Print`-scaffolding
+/// function that writes every field of the active variant, so a pass
+/// adding a node kind gets a working (if unadorned) snapshot rendering
+/// before anyone hand-tunes its formatting.
+pub fn generate_print(schema: &Schema) -> String {
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push_str("\nuse std::fmt::{self, Write};\n\nuse super::nodes::*;\n");
+
+    for node in &schema.nodes {
+        let node_name = &node.name;
+        let snake = to_snake_case(node_name);
+
+        out.push_str(&format!(
+            "\n/// Writes a debug-ish textual form of `{node_name}` to `out`.\n"
+        ));
+        out.push_str(&format!(
+            "pub fn print_{snake}(node: &{node_name}, out: &mut impl Write) -> fmt::Result {{\n"
+        ));
+        out.push_str("    match node {\n");
+        for variant in &node.variants {
+            if variant.fields.is_empty() {
+                out.push_str(&format!(
+                    "        {node_name}::{} => write!(out, \"{}\"),\n",
+                    variant.name, variant.name
+                ));
+            } else {
+                let bindings = variant
+                    .fields
+                    .iter()
+                    .map(|(field_name, _)| field_name.clone())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let format_str = variant
+                    .fields
+                    .iter()
+                    .map(|(field_name, _)| format!("{field_name}: {{{field_name}:?}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                out.push_str(&format!(
+                    "        {node_name}::{} {{ {bindings} }} => write!(out, \"{}({format_str})\"),\n",
+                    variant.name, variant.name
+                ));
+            }
+        }
+        out.push_str("    }\n");
+        out.push_str("}\n");
+    }
+    out
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (ix, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if ix != 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
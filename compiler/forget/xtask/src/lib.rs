@@ -0,0 +1,6 @@
+//! Shared logic behind `cargo xtask`'s subcommands, split out of
+//! `main.rs` so the regeneration check (`tests/codegen_check.rs`) can
+//! call it directly instead of shelling out to the binary.
+
+pub mod codegen;
+pub mod schema;
@@ -0,0 +1,97 @@
+//! Developer-facing codegen tasks, invoked as `cargo xtask <command>`.
+//!
+//! Modeled on rust-analyzer's `xtask`: a plain binary crate (no Cargo
+//! plugin magic), run via `cargo run -p xtask -- codegen`. Paths are
+//! resolved relative to this crate's manifest directory (via
+//! `CARGO_MANIFEST_DIR`) rather than the caller's cwd, so the command
+//! gives the same result from anywhere in the tree.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use xtask::codegen;
+use xtask::schema::Schema;
+
+fn xtask_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn schema_path() -> PathBuf {
+    xtask_dir().join("schema/hir_nodes.ron")
+}
+
+fn nodes_out() -> PathBuf {
+    xtask_dir().join("../crates/forget_hir/src/generated/nodes.rs")
+}
+
+fn visitor_out() -> PathBuf {
+    xtask_dir().join("../crates/forget_hir/src/generated/visitor.rs")
+}
+
+fn print_out() -> PathBuf {
+    xtask_dir().join("../crates/forget_hir/src/generated/print.rs")
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("codegen") => {
+            let check = args.any(|arg| arg == "--check");
+            if let Err(error) = codegen(check) {
+                eprintln!("error: {error}");
+                process::exit(1);
+            }
+        }
+        _ => {
+            eprintln!("usage: cargo xtask codegen [--check]");
+            process::exit(1);
+        }
+    }
+}
+
+/// Regenerates `nodes.rs`, `visitor.rs` and `print.rs` from the schema.
+/// With `--check`, instead compares the freshly generated text against
+/// what is already on disk and exits non-zero on any difference, so a
+/// stale checkout fails CI the way rust-analyzer's `boilerplate_gen`
+/// does.
+fn codegen(check: bool) -> Result<(), String> {
+    let schema_path = schema_path();
+    let schema_text = fs::read_to_string(&schema_path)
+        .map_err(|error| format!("reading {}: {error}", schema_path.display()))?;
+    let schema: Schema = ron::from_str(&schema_text)
+        .map_err(|error| format!("parsing {}: {error}", schema_path.display()))?;
+
+    let nodes = codegen::generate_nodes(&schema);
+    let visitor = codegen::generate_visitor(&schema);
+    let print = codegen::generate_print(&schema);
+
+    if check {
+        check_up_to_date(&nodes_out(), &nodes)?;
+        check_up_to_date(&visitor_out(), &visitor)?;
+        check_up_to_date(&print_out(), &print)?;
+    } else {
+        write(&nodes_out(), &nodes)?;
+        write(&visitor_out(), &visitor)?;
+        write(&print_out(), &print)?;
+    }
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| format!("creating {parent:?}: {error}"))?;
+    }
+    fs::write(path, contents).map_err(|error| format!("writing {}: {error}", path.display()))
+}
+
+fn check_up_to_date(path: &Path, expected: &str) -> Result<(), String> {
+    let actual = fs::read_to_string(path)
+        .map_err(|error| format!("reading {}: {error}", path.display()))?;
+    if actual != expected {
+        return Err(format!(
+            "{} is stale; run `cargo xtask codegen` and commit the result",
+            path.display()
+        ));
+    }
+    Ok(())
+}
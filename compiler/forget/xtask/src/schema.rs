@@ -0,0 +1,28 @@
+//! Types mirroring the RON schema files under `xtask/schema/`.
+//!
+//! Kept deliberately small: a node is an enum, a variant is a case, and
+//! a field is just a `(name, type)` pair whose type is spliced verbatim
+//! into the generated code. There is no inheritance or generics in the
+//! schema itself — that complexity belongs in the generated Rust, not
+//! in the thing describing it.
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Schema {
+    pub nodes: Vec<NodeDef>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeDef {
+    pub name: String,
+    pub doc: String,
+    pub variants: Vec<VariantDef>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VariantDef {
+    pub name: String,
+    pub doc: String,
+    pub fields: Vec<(String, String)>,
+}
@@ -0,0 +1,42 @@
+//! Regenerates the HIR node/visitor modules from the committed schema
+//! and diffs the result against what's checked in under
+//! `forget_hir/src/generated/`, the way rust-analyzer's
+//! `boilerplate_gen` test catches a checkout that forgot to rerun
+//! codegen after editing the schema.
+
+use std::fs;
+
+use xtask::codegen;
+use xtask::schema::Schema;
+
+const SCHEMA_PATH: &str = "schema/hir_nodes.ron";
+const NODES_OUT: &str = "../crates/forget_hir/src/generated/nodes.rs";
+const VISITOR_OUT: &str = "../crates/forget_hir/src/generated/visitor.rs";
+const PRINT_OUT: &str = "../crates/forget_hir/src/generated/print.rs";
+
+#[test]
+fn generated_hir_nodes_are_up_to_date() {
+    let schema_text = fs::read_to_string(SCHEMA_PATH).expect("read schema");
+    let schema: Schema = ron::from_str(&schema_text).expect("parse schema");
+
+    let expected_nodes = codegen::generate_nodes(&schema);
+    let actual_nodes = fs::read_to_string(NODES_OUT).expect("read committed nodes.rs");
+    assert_eq!(
+        actual_nodes, expected_nodes,
+        "forget_hir/src/generated/nodes.rs is stale; run `cargo xtask codegen` and commit the result"
+    );
+
+    let expected_visitor = codegen::generate_visitor(&schema);
+    let actual_visitor = fs::read_to_string(VISITOR_OUT).expect("read committed visitor.rs");
+    assert_eq!(
+        actual_visitor, expected_visitor,
+        "forget_hir/src/generated/visitor.rs is stale; run `cargo xtask codegen` and commit the result"
+    );
+
+    let expected_print = codegen::generate_print(&schema);
+    let actual_print = fs::read_to_string(PRINT_OUT).expect("read committed print.rs");
+    assert_eq!(
+        actual_print, expected_print,
+        "forget_hir/src/generated/print.rs is stale; run `cargo xtask codegen` and commit the result"
+    );
+}